@@ -0,0 +1,227 @@
+/// RNNoise-style spectral gating denoiser.
+///
+/// This is not the actual RNNoise recurrent network (that requires the
+/// bundled model weights); it follows the same frame pipeline RNNoise uses
+/// around its gain estimator - fixed-size overlapping frames, a windowed
+/// forward transform, a per-band noise floor, and overlap-add resynthesis -
+/// but estimates the per-band gain with a simple adaptive noise floor
+/// instead of a trained network. Good enough to push steady background
+/// noise (fans, AC, room tone) below the silence threshold without
+/// disturbing the samples kept for export.
+const FRAME_SIZE: usize = 512; // nearest power of two to RNNoise's native 480-sample (10ms @ 48kHz) frame, so the forward/inverse transform can be a radix-2 FFT
+const HOP_SIZE: usize = FRAME_SIZE / 2; // 50% overlap for overlap-add
+
+/// Runs a cleaned copy of `samples` (normalized to -1.0..1.0) through
+/// frame-based spectral gating. The input is left untouched; callers that
+/// need the original samples (e.g. for export) should keep their own copy.
+pub fn denoise_samples(samples: &[f32], sample_rate: u32) -> Vec<f32> {
+    if samples.len() < FRAME_SIZE {
+        return samples.to_vec();
+    }
+
+    let window = hann_window(FRAME_SIZE);
+    let num_bins = FRAME_SIZE / 2 + 1;
+    let mut noise_floor = vec![0.0f64; num_bins];
+    let mut floor_initialized = false;
+
+    let mut output = vec![0.0f64; samples.len()];
+    let mut weight_sum = vec![0.0f64; samples.len()];
+
+    let mut start = 0usize;
+    while start + FRAME_SIZE <= samples.len() {
+        let frame: Vec<f64> = samples[start..start + FRAME_SIZE]
+            .iter()
+            .zip(&window)
+            .map(|(&s, &w)| (s as f64) * w)
+            .collect();
+
+        let (real, imag) = forward_fft(&frame);
+        let magnitude: Vec<f64> = real
+            .iter()
+            .zip(&imag)
+            .map(|(re, im)| (re * re + im * im).sqrt())
+            .collect();
+
+        if !floor_initialized {
+            noise_floor.copy_from_slice(&magnitude[..num_bins]);
+            floor_initialized = true;
+        } else {
+            // Track the noise floor per band: rise slowly (in case the
+            // frame genuinely is noise-only), fall fast when speech appears
+            // and the band goes quiet again.
+            for bin in 0..num_bins {
+                let mag = magnitude[bin];
+                if mag < noise_floor[bin] {
+                    noise_floor[bin] = mag;
+                } else {
+                    noise_floor[bin] += (mag - noise_floor[bin]) * 0.05;
+                }
+            }
+        }
+
+        // Spectral-subtraction style gain: suppress bands close to the
+        // noise floor, pass bands well above it through untouched.
+        let mut gained_real = real.clone();
+        let mut gained_imag = imag.clone();
+        for bin in 0..num_bins {
+            let mag = magnitude[bin].max(1e-9);
+            let gain = (1.0 - (noise_floor[bin] * 2.0) / mag).clamp(0.05, 1.0);
+            gained_real[bin] *= gain;
+            gained_imag[bin] *= gain;
+            if bin > 0 && bin < FRAME_SIZE - bin {
+                gained_real[FRAME_SIZE - bin] = gained_real[bin];
+                gained_imag[FRAME_SIZE - bin] = -gained_imag[bin];
+            }
+        }
+
+        let resynthesized = inverse_fft(&gained_real, &gained_imag);
+
+        for i in 0..FRAME_SIZE {
+            output[start + i] += resynthesized[i] * window[i];
+            weight_sum[start + i] += window[i] * window[i];
+        }
+
+        start += HOP_SIZE;
+    }
+
+    let _ = sample_rate; // frame size is fixed regardless of source rate
+
+    output
+        .iter()
+        .zip(&weight_sum)
+        .map(|(&sample, &weight)| {
+            let normalized = if weight > 1e-9 { sample / weight } else { 0.0 };
+            normalized.clamp(-1.0, 1.0) as f32
+        })
+        .collect()
+}
+
+fn hann_window(size: usize) -> Vec<f64> {
+    (0..size)
+        .map(|i| 0.5 - 0.5 * (2.0 * std::f64::consts::PI * i as f64 / (size - 1) as f64).cos())
+        .collect()
+}
+
+/// Forward real-input FFT. `FRAME_SIZE` is a power of two so this is a
+/// straightforward iterative radix-2 Cooley-Tukey transform: O(n log n)
+/// instead of the O(n^2) a direct DFT would cost run twice per frame.
+fn forward_fft(frame: &[f64]) -> (Vec<f64>, Vec<f64>) {
+    let mut real = frame.to_vec();
+    let mut imag = vec![0.0; frame.len()];
+    fft_in_place(&mut real, &mut imag, false);
+    (real, imag)
+}
+
+/// Inverse FFT, returning only the (real-valued, since the spectrum going in
+/// is Hermitian-symmetric) time-domain signal.
+fn inverse_fft(real: &[f64], imag: &[f64]) -> Vec<f64> {
+    let mut real = real.to_vec();
+    let mut imag = imag.to_vec();
+    fft_in_place(&mut real, &mut imag, true);
+    real
+}
+
+/// In-place iterative radix-2 Cooley-Tukey FFT (decimation-in-time). `real`
+/// and `imag` must have the same power-of-two length. Runs the inverse
+/// transform (conjugated twiddles, 1/n scaling) when `invert` is set.
+fn fft_in_place(real: &mut [f64], imag: &mut [f64], invert: bool) {
+    let n = real.len();
+    debug_assert!(n.is_power_of_two());
+
+    // Bit-reversal permutation so the butterfly stages below can work in place
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            real.swap(i, j);
+            imag.swap(i, j);
+        }
+    }
+
+    let mut len = 2;
+    while len <= n {
+        let angle = 2.0 * std::f64::consts::PI / len as f64 * if invert { 1.0 } else { -1.0 };
+        let (wlen_re, wlen_im) = (angle.cos(), angle.sin());
+
+        let mut start = 0;
+        while start < n {
+            let mut w_re = 1.0;
+            let mut w_im = 0.0;
+            for k in 0..len / 2 {
+                let (lo, hi) = (start + k, start + k + len / 2);
+                let v_re = real[hi] * w_re - imag[hi] * w_im;
+                let v_im = real[hi] * w_im + imag[hi] * w_re;
+
+                real[hi] = real[lo] - v_re;
+                imag[hi] = imag[lo] - v_im;
+                real[lo] += v_re;
+                imag[lo] += v_im;
+
+                let next_w_re = w_re * wlen_re - w_im * wlen_im;
+                w_im = w_re * wlen_im + w_im * wlen_re;
+                w_re = next_w_re;
+            }
+            start += len;
+        }
+
+        len <<= 1;
+    }
+
+    if invert {
+        for (re, im) in real.iter_mut().zip(imag.iter_mut()) {
+            *re /= n as f64;
+            *im /= n as f64;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_denoise_preserves_length() {
+        let samples = vec![0.0f32; FRAME_SIZE * 4];
+        let cleaned = denoise_samples(&samples, 48000);
+        assert_eq!(cleaned.len(), samples.len());
+    }
+
+    #[test]
+    fn test_denoise_short_input_passthrough() {
+        let samples = vec![0.01f32, 0.02, 0.03];
+        let cleaned = denoise_samples(&samples, 48000);
+        assert_eq!(cleaned, samples);
+    }
+
+    #[test]
+    fn test_fft_round_trip_recovers_input() {
+        let frame: Vec<f64> = (0..FRAME_SIZE).map(|i| (i as f64 * 0.01).sin()).collect();
+        let (real, imag) = forward_fft(&frame);
+        let recovered = inverse_fft(&real, &imag);
+
+        for (a, b) in frame.iter().zip(&recovered) {
+            assert!((a - b).abs() < 1e-9, "expected {} got {}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_denoise_suppresses_steady_hiss() {
+        // Low-level steady noise should be attenuated once the floor adapts.
+        let mut samples = Vec::with_capacity(FRAME_SIZE * 20);
+        let mut seed = 12345u32;
+        for _ in 0..FRAME_SIZE * 20 {
+            seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+            let noise = (((seed >> 16) % 200) as f32 - 100.0) / 32768.0;
+            samples.push(noise);
+        }
+        let cleaned = denoise_samples(&samples, 48000);
+        let input_energy: f64 = samples.iter().map(|&s| (s as f64).powi(2)).sum();
+        let output_energy: f64 = cleaned.iter().map(|&s| (s as f64).powi(2)).sum();
+        assert!(output_energy < input_energy);
+    }
+}
@@ -1,6 +1,10 @@
+mod denoise;
+pub mod scene_detection;
 pub mod silence_detection;
+mod wav_samples;
 pub mod waveform;
 
+pub use scene_detection::{detect_scene_changes, SceneSnapConfig, SCENE_DETECTION_THRESHOLD};
 pub use silence_detection::{detect_silence, SilenceDetectionConfig};
 pub use waveform::{extract_waveform, WaveformData};
 
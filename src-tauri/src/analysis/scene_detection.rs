@@ -0,0 +1,83 @@
+use std::path::Path;
+use std::process::Command;
+use serde::{Deserialize, Serialize};
+
+/// FFmpeg scene-score threshold above which a frame is considered a shot
+/// change. Shared by the snap-on-detect pipeline and the `get_scene_boundaries`
+/// command so the boundaries the frontend visualizes always match the ones
+/// actually used for snapping.
+pub const SCENE_DETECTION_THRESHOLD: f64 = 0.4;
+
+/// Controls whether/how silence-derived cut points snap to nearby scene changes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SceneSnapConfig {
+    /// Whether cuts should be snapped to scene boundaries at all
+    pub enabled: bool,
+    /// How close (in seconds) a scene boundary must be to a cut for it to snap
+    pub tolerance_seconds: f64,
+}
+
+impl Default for SceneSnapConfig {
+    fn default() -> Self {
+        SceneSnapConfig {
+            enabled: false,
+            tolerance_seconds: 0.25,
+        }
+    }
+}
+
+/// Detects shot/scene-change timestamps in a video using FFmpeg's scene-score filter.
+/// Runs `select='gt(scene,threshold)',showinfo` and parses showinfo's `pts_time` fields
+/// from stderr, since FFmpeg only writes the decoded frames to the null muxer.
+pub fn detect_scene_changes(
+    video_path: &str,
+    ffmpeg_path: &Path,
+    threshold: f64,
+) -> Result<Vec<f64>, String> {
+    log::info!("Detecting scene changes in: {} (threshold {})", video_path, threshold);
+
+    let filter = format!("select='gt(scene,{})',showinfo", threshold);
+
+    let output = Command::new(ffmpeg_path)
+        .args(&[
+            "-i", video_path,
+            "-vf", &filter,
+            "-an",
+            "-f", "null",
+            "-",
+        ])
+        .output()
+        .map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                "FFmpeg not found. Please ensure FFmpeg is installed and in your PATH.".to_string()
+            } else {
+                format!("Failed to execute FFmpeg: {}", e)
+            }
+        })?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let mut scene_times: Vec<f64> = stderr
+        .lines()
+        .filter_map(|line| {
+            let pts_time = line.split("pts_time:").nth(1)?;
+            pts_time.split_whitespace().next()?.parse::<f64>().ok()
+        })
+        .collect();
+
+    scene_times.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    log::info!("Detected {} scene changes", scene_times.len());
+
+    Ok(scene_times)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_scene_snap_config() {
+        let config = SceneSnapConfig::default();
+        assert_eq!(config.enabled, false);
+        assert_eq!(config.tolerance_seconds, 0.25);
+    }
+}
@@ -1,14 +1,49 @@
 use hound::{WavReader, WavSpec};
+use std::borrow::Cow;
 use std::path::Path;
 use serde::{Deserialize, Serialize};
 
+use crate::analysis::denoise::denoise_samples;
+use crate::analysis::wav_samples::read_normalized_samples;
+
+/// Which signal model is used to decide whether a window is silent
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ThresholdMode {
+    /// Raw RMS amplitude compared against a dB threshold (legacy behavior)
+    Rms,
+    /// Perceptual loudness (EBU R128 / ITU-R BS.1770) compared against a LUFS threshold
+    Lufs,
+}
+
+impl Default for ThresholdMode {
+    fn default() -> Self {
+        ThresholdMode::Rms
+    }
+}
+
 /// Configuration for silence detection
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SilenceDetectionConfig {
-    /// Silence threshold in dB (e.g., -35.0)
+    /// Silence threshold in dB (e.g., -35.0), used when `threshold_mode` is `Rms`
     pub threshold_db: f64,
     /// Minimum silence duration in seconds (e.g., 0.3 for 300ms)
     pub min_silence_duration: f64,
+    /// Which model is used to judge silence
+    #[serde(default)]
+    pub threshold_mode: ThresholdMode,
+    /// Loudness threshold in LUFS (e.g., -50.0), used when `threshold_mode` is `Lufs`
+    #[serde(default = "default_threshold_lufs")]
+    pub threshold_lufs: f64,
+    /// When true, run a denoised copy of the samples through detection so
+    /// steady background noise (fans, AC, room tone) doesn't raise the
+    /// effective floor above the configured threshold. The original samples
+    /// are never modified; this only affects what silence detection "hears".
+    #[serde(default)]
+    pub denoise: bool,
+}
+
+fn default_threshold_lufs() -> f64 {
+    -50.0
 }
 
 impl Default for SilenceDetectionConfig {
@@ -16,6 +51,9 @@ impl Default for SilenceDetectionConfig {
         SilenceDetectionConfig {
             threshold_db: -35.0,
             min_silence_duration: 0.3,
+            threshold_mode: ThresholdMode::Rms,
+            threshold_lufs: default_threshold_lufs(),
+            denoise: false,
         }
     }
 }
@@ -27,72 +65,146 @@ pub fn detect_silence(
     config: &SilenceDetectionConfig,
 ) -> Result<Vec<(f64, f64)>, String> {
     log::info!("Starting silence detection on: {:?}", wav_path);
-    log::info!("Threshold: {} dB, Min duration: {} s", 
-               config.threshold_db, config.min_silence_duration);
-    
+    log::info!("Mode: {:?}, Min duration: {} s", config.threshold_mode, config.min_silence_duration);
+
     // Open WAV file
     let mut reader = WavReader::open(wav_path)
         .map_err(|e| format!("Failed to open WAV file: {}", e))?;
-    
+
     let spec = reader.spec();
     log::info!("WAV spec: {:?}", spec);
-    
-    // Read all samples
-    let samples: Vec<i16> = reader
-        .samples::<i16>()
-        .collect::<Result<Vec<_>, _>>()
-        .map_err(|e| format!("Failed to read samples: {}", e))?;
-    
+
+    // Read all samples, normalized to -1.0..1.0 regardless of the WAV's
+    // underlying bit depth / sample format.
+    let samples: Vec<f32> = read_normalized_samples(&mut reader)?;
+
     if samples.is_empty() {
         return Err("WAV file contains no samples".to_string());
     }
-    
+
     log::info!("Read {} samples", samples.len());
-    
-    // Calculate silence using RMS analysis
-    let silence_ranges = analyze_silence_rms(&samples, &spec, config);
-    
+
+    // Denoising only affects what detection "hears"; the samples returned
+    // here are never used for export, so there's nothing else to keep in sync.
+    let detection_samples: Cow<[f32]> = if config.denoise {
+        log::info!("Denoising samples before silence detection");
+        Cow::Owned(denoise_samples(&samples, spec.sample_rate))
+    } else {
+        Cow::Borrowed(&samples)
+    };
+
+    let silence_ranges = match config.threshold_mode {
+        ThresholdMode::Rms => analyze_silence_rms(&detection_samples, &spec, config),
+        ThresholdMode::Lufs => analyze_silence_lufs(&detection_samples, &spec, config),
+    };
+
     log::info!("Detected {} silence ranges", silence_ranges.len());
     for (i, (start, end)) in silence_ranges.iter().enumerate() {
         log::info!("  Silence {}: {:.2}s - {:.2}s ({:.2}s)", i + 1, start, end, end - start);
     }
-    
+
     Ok(silence_ranges)
 }
 
 /// Analyzes audio samples using RMS (Root Mean Square) to detect silence
 fn analyze_silence_rms(
-    samples: &[i16],
+    samples: &[f32],
     spec: &WavSpec,
     config: &SilenceDetectionConfig,
 ) -> Vec<(f64, f64)> {
     let sample_rate = spec.sample_rate as f64;
-    
+
     // Use 10ms windows for analysis (typical for audio analysis)
     let window_size = (sample_rate * 0.01) as usize; // 10ms
-    
+
     if window_size == 0 {
         log::error!("Window size is 0, sample rate might be too low");
         return Vec::new();
     }
-    
-    let mut silence_ranges = Vec::new();
-    let mut silence_start: Option<f64> = None;
-    
+
     // Convert threshold from dB to amplitude
     // dB = 20 * log10(amplitude / max_amplitude)
-    // For 16-bit audio, max amplitude is 32768
-    let threshold_amplitude = 32768.0 * 10f64.powf(config.threshold_db / 20.0);
-    
+    // Samples are normalized to -1.0..1.0, so max amplitude is 1.0
+    let threshold_amplitude = 10f64.powf(config.threshold_db / 20.0);
+
     log::debug!("Threshold amplitude: {:.2}", threshold_amplitude);
-    
-    // Analyze each window
-    for (i, window) in samples.chunks(window_size).enumerate() {
-        let rms = calculate_rms(window);
-        let time = (i * window_size) as f64 / sample_rate;
-        
-        let is_silent = rms < threshold_amplitude;
-        
+
+    let windows: Vec<(f64, bool)> = samples
+        .chunks(window_size)
+        .enumerate()
+        .map(|(i, window)| {
+            let rms = calculate_rms(window);
+            let time = (i * window_size) as f64 / sample_rate;
+            (time, rms < threshold_amplitude)
+        })
+        .collect();
+
+    let end_time = samples.len() as f64 / sample_rate;
+    merge_silent_windows(&windows, end_time, config.min_silence_duration)
+}
+
+/// Analyzes audio samples using EBU R128 / ITU-R BS.1770 perceptual loudness to detect silence
+fn analyze_silence_lufs(
+    samples: &[f32],
+    spec: &WavSpec,
+    config: &SilenceDetectionConfig,
+) -> Vec<(f64, f64)> {
+    let sample_rate = spec.sample_rate as f64;
+
+    // K-weight the whole signal up front, carrying biquad state across the full
+    // sample buffer so the high-pass stage doesn't reset at window boundaries.
+    let mut pre_filter = Biquad::k_weighting_pre_filter(sample_rate);
+    let mut rlb_filter = Biquad::k_weighting_rlb(sample_rate);
+    let weighted: Vec<f64> = samples
+        .iter()
+        .map(|&s| {
+            let x = s as f64;
+            rlb_filter.process(pre_filter.process(x))
+        })
+        .collect();
+
+    // Momentary loudness: 400ms windows, ~75% overlap (100ms hop)
+    let window_size = (sample_rate * 0.4) as usize;
+    let hop_size = (sample_rate * 0.1) as usize;
+
+    if window_size == 0 || hop_size == 0 {
+        log::error!("Window size is 0, sample rate might be too low");
+        return Vec::new();
+    }
+
+    const MEAN_SQUARE_EPSILON: f64 = 1e-12;
+
+    let mut windows = Vec::new();
+    let mut start = 0usize;
+    while start < weighted.len() {
+        let end = (start + window_size).min(weighted.len());
+        let window = &weighted[start..end];
+
+        let mean_square = (window.iter().map(|v| v * v).sum::<f64>() / window.len() as f64)
+            .max(MEAN_SQUARE_EPSILON);
+        let loudness_lufs = -0.691 + 10.0 * mean_square.log10();
+
+        let time = start as f64 / sample_rate;
+        windows.push((time, loudness_lufs < config.threshold_lufs));
+
+        start += hop_size;
+    }
+
+    let end_time = samples.len() as f64 / sample_rate;
+    merge_silent_windows(&windows, end_time, config.min_silence_duration)
+}
+
+/// Turns a sequence of (window_start_time, is_silent) pairs into merged
+/// (start, end) silence ranges, dropping any run shorter than `min_silence_duration`.
+fn merge_silent_windows(
+    windows: &[(f64, bool)],
+    end_time: f64,
+    min_silence_duration: f64,
+) -> Vec<(f64, f64)> {
+    let mut silence_ranges = Vec::new();
+    let mut silence_start: Option<f64> = None;
+
+    for &(time, is_silent) in windows {
         match (is_silent, silence_start) {
             (true, None) => {
                 // Start of new silence region
@@ -101,7 +213,7 @@ fn analyze_silence_rms(
             (false, Some(start)) => {
                 // End of silence region
                 let duration = time - start;
-                if duration >= config.min_silence_duration {
+                if duration >= min_silence_duration {
                     silence_ranges.push((start, time));
                 }
                 silence_start = None;
@@ -109,25 +221,94 @@ fn analyze_silence_rms(
             _ => {}
         }
     }
-    
+
     // Handle silence that extends to the end of the file
     if let Some(start) = silence_start {
-        let end_time = samples.len() as f64 / sample_rate;
         let duration = end_time - start;
-        if duration >= config.min_silence_duration {
+        if duration >= min_silence_duration {
             silence_ranges.push((start, end_time));
         }
     }
-    
+
     silence_ranges
 }
 
+/// A single second-order IIR section (Direct Form I) used to build the
+/// K-weighting filter chain, with state carried across calls to `process`.
+struct Biquad {
+    b0: f64,
+    b1: f64,
+    b2: f64,
+    a1: f64,
+    a2: f64,
+    x1: f64,
+    x2: f64,
+    y1: f64,
+    y2: f64,
+}
+
+impl Biquad {
+    fn new(b0: f64, b1: f64, b2: f64, a1: f64, a2: f64) -> Self {
+        Biquad { b0, b1, b2, a1, a2, x1: 0.0, x2: 0.0, y1: 0.0, y2: 0.0 }
+    }
+
+    fn process(&mut self, x0: f64) -> f64 {
+        let y0 = self.b0 * x0 + self.b1 * self.x1 + self.b2 * self.x2
+            - self.a1 * self.y1 - self.a2 * self.y2;
+        self.x2 = self.x1;
+        self.x1 = x0;
+        self.y2 = self.y1;
+        self.y1 = y0;
+        y0
+    }
+
+    /// Stage 1 of K-weighting: a high-shelf "pre-filter" boosting highs by
+    /// roughly +4 dB above ~1.5 kHz, per ITU-R BS.1770 / EBU Tech 3341.
+    /// Coefficients come from the filter's analog design parameters via the
+    /// bilinear transform, so they stay correct at any sample rate.
+    fn k_weighting_pre_filter(sample_rate: f64) -> Self {
+        let f0 = 1681.974_450_955_533_2;
+        let g = 3.999_843_853_97_f64;
+        let q = 0.707_175_236_955_419_6;
+
+        let k = (std::f64::consts::PI * f0 / sample_rate).tan();
+        let vh = 10f64.powf(g / 20.0);
+        let vb = vh.powf(0.499_666_774_155);
+
+        let a0 = 1.0 + k / q + k * k;
+        let b0 = (vh + vb * k / q + k * k) / a0;
+        let b1 = 2.0 * (k * k - vh) / a0;
+        let b2 = (vh - vb * k / q + k * k) / a0;
+        let a1 = 2.0 * (k * k - 1.0) / a0;
+        let a2 = (1.0 - k / q + k * k) / a0;
+
+        Biquad::new(b0, b1, b2, a1, a2)
+    }
+
+    /// Stage 2 of K-weighting: the RLB (Revised Low-frequency B) curve, a
+    /// ~38 Hz second-order high-pass, per ITU-R BS.1770.
+    fn k_weighting_rlb(sample_rate: f64) -> Self {
+        let f0 = 38.135_470_876_139_82;
+        let q = 0.500_327_037_325_395_3;
+
+        let k = (std::f64::consts::PI * f0 / sample_rate).tan();
+        let a0 = 1.0 + k / q + k * k;
+        let b0 = 1.0 / a0;
+        let b1 = -2.0 / a0;
+        let b2 = 1.0 / a0;
+        let a1 = 2.0 * (k * k - 1.0) / a0;
+        let a2 = (1.0 - k / q + k * k) / a0;
+
+        Biquad::new(b0, b1, b2, a1, a2)
+    }
+}
+
 /// Calculates the Root Mean Square (RMS) of a set of audio samples
-fn calculate_rms(samples: &[i16]) -> f64 {
+fn calculate_rms(samples: &[f32]) -> f64 {
     if samples.is_empty() {
         return 0.0;
     }
-    
+
     let sum_of_squares: f64 = samples
         .iter()
         .map(|&s| {
@@ -135,7 +316,7 @@ fn calculate_rms(samples: &[i16]) -> f64 {
             s_f64 * s_f64
         })
         .sum();
-    
+
     (sum_of_squares / samples.len() as f64).sqrt()
 }
 
@@ -145,14 +326,14 @@ mod tests {
 
     #[test]
     fn test_rms_calculation() {
-        let samples = vec![100, -100, 100, -100];
+        let samples = vec![0.1f32, -0.1, 0.1, -0.1];
         let rms = calculate_rms(&samples);
-        assert_eq!(rms, 100.0);
+        assert!((rms - 0.1).abs() < 1e-6);
     }
 
     #[test]
     fn test_rms_silence() {
-        let samples = vec![0, 0, 0, 0];
+        let samples = vec![0.0f32, 0.0, 0.0, 0.0];
         let rms = calculate_rms(&samples);
         assert_eq!(rms, 0.0);
     }
@@ -162,5 +343,26 @@ mod tests {
         let config = SilenceDetectionConfig::default();
         assert_eq!(config.threshold_db, -35.0);
         assert_eq!(config.min_silence_duration, 0.3);
+        assert_eq!(config.threshold_mode, ThresholdMode::Rms);
+        assert_eq!(config.denoise, false);
+    }
+
+    #[test]
+    fn test_lufs_silence_on_digital_zero() {
+        let spec = WavSpec {
+            channels: 1,
+            sample_rate: 48000,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let config = SilenceDetectionConfig {
+            threshold_mode: ThresholdMode::Lufs,
+            min_silence_duration: 0.2,
+            ..SilenceDetectionConfig::default()
+        };
+        let samples = vec![0.0f32; 48000]; // 1s of pure digital silence
+        let ranges = analyze_silence_lufs(&samples, &spec, &config);
+        assert_eq!(ranges.len(), 1);
+        assert_eq!(ranges[0].0, 0.0);
     }
 }
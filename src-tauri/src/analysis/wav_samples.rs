@@ -0,0 +1,69 @@
+use hound::{SampleFormat, WavReader};
+use std::io::Read;
+
+/// Reads every sample in a WAV file and normalizes it to the common f32
+/// range of -1.0..1.0, regardless of whether the file is 16/24/32-bit PCM
+/// or 32-bit float. Both silence detection and waveform extraction need
+/// this, since the ffmpeg sidecar isn't guaranteed to emit 16-bit PCM.
+pub(crate) fn read_normalized_samples<R: Read>(reader: &mut WavReader<R>) -> Result<Vec<f32>, String> {
+    let spec = reader.spec();
+
+    match spec.sample_format {
+        SampleFormat::Float => reader
+            .samples::<f32>()
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to read float samples: {}", e)),
+        SampleFormat::Int => {
+            // hound sign-extends integer samples of any bit depth into i32,
+            // so reading as i32 and normalizing by the format's own full
+            // scale works uniformly for 16/24/32-bit PCM.
+            let max_amplitude = (1i64 << (spec.bits_per_sample - 1)) as f32;
+            reader
+                .samples::<i32>()
+                .map(|s| s.map(|v| v as f32 / max_amplitude))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| format!("Failed to read integer samples: {}", e))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hound::WavSpec;
+    use std::io::Cursor;
+
+    fn write_wav(spec: WavSpec, samples: &[f32]) -> Vec<u8> {
+        let mut cursor = Cursor::new(Vec::new());
+        {
+            let mut writer = hound::WavWriter::new(&mut cursor, spec).unwrap();
+            for &s in samples {
+                match spec.sample_format {
+                    SampleFormat::Float => writer.write_sample(s).unwrap(),
+                    SampleFormat::Int => writer.write_sample((s * i16::MAX as f32) as i16).unwrap(),
+                }
+            }
+            writer.finalize().unwrap();
+        }
+        cursor.into_inner()
+    }
+
+    #[test]
+    fn test_normalizes_16_bit_int() {
+        let spec = WavSpec { channels: 1, sample_rate: 48000, bits_per_sample: 16, sample_format: SampleFormat::Int };
+        let bytes = write_wav(spec, &[0.5, -0.5]);
+        let mut reader = WavReader::new(Cursor::new(bytes)).unwrap();
+        let samples = read_normalized_samples(&mut reader).unwrap();
+        assert!((samples[0] - 0.5).abs() < 0.001);
+        assert!((samples[1] + 0.5).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_normalizes_32_bit_float() {
+        let spec = WavSpec { channels: 1, sample_rate: 48000, bits_per_sample: 32, sample_format: SampleFormat::Float };
+        let bytes = write_wav(spec, &[0.25, -0.75]);
+        let mut reader = WavReader::new(Cursor::new(bytes)).unwrap();
+        let samples = read_normalized_samples(&mut reader).unwrap();
+        assert_eq!(samples, vec![0.25, -0.75]);
+    }
+}
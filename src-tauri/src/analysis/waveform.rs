@@ -2,6 +2,8 @@ use hound::WavReader;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 
+use crate::analysis::wav_samples::read_normalized_samples;
+
 /// Waveform data for UI visualization
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WaveformData {
@@ -29,39 +31,33 @@ pub fn extract_waveform(wav_path: &Path, bucket_ms: u32) -> Result<WaveformData,
         return Err("Bucket size too small for sample rate".to_string());
     }
     
-    // Read all samples
-    let samples: Vec<i16> = reader
-        .samples::<i16>()
-        .collect::<Result<Vec<_>, _>>()
-        .map_err(|e| format!("Failed to read samples: {}", e))?;
-    
+    // Read all samples, normalized to -1.0..1.0 regardless of the WAV's
+    // underlying bit depth / sample format.
+    let samples: Vec<f32> = read_normalized_samples(&mut reader)?;
+
     if samples.is_empty() {
         return Err("WAV file contains no samples".to_string());
     }
-    
+
     let duration = samples.len() as f64 / sample_rate / spec.channels as f64;
-    
+
     // Calculate peak for each bucket
-    let mono_samples: Vec<i16> = if spec.channels > 1 {
+    let mono_samples: Vec<f32> = if spec.channels > 1 {
         // Average channels to mono
         samples.chunks(spec.channels as usize)
-            .map(|chunk| {
-                let sum: i32 = chunk.iter().map(|&s| s as i32).sum();
-                (sum / chunk.len() as i32) as i16
-            })
+            .map(|chunk| chunk.iter().sum::<f32>() / chunk.len() as f32)
             .collect()
     } else {
         samples
     };
-    
+
     let peaks: Vec<f32> = mono_samples
         .chunks(samples_per_bucket)
         .map(|bucket| {
-            let max_val = bucket.iter()
-                .map(|&s| s.abs() as f32)
-                .fold(0.0f32, |a, b| a.max(b));
-            // Normalize to 0.0-1.0
-            max_val / 32768.0
+            // Samples are already normalized to -1.0..1.0
+            bucket.iter()
+                .map(|&s| s.abs())
+                .fold(0.0f32, |a, b| a.max(b))
         })
         .collect();
     
@@ -1,39 +1,44 @@
 use crate::media::{extract_audio_to_wav, get_video_duration};
-use crate::analysis::{detect_silence, SilenceDetectionConfig};
+use crate::analysis::{detect_scene_changes, detect_silence, SceneSnapConfig, SilenceDetectionConfig, SCENE_DETECTION_THRESHOLD};
 use crate::timeline::Timeline;
 use std::path::PathBuf;
 use std::env;
 
-
-
 /// Orchestrates video processing and returns both Timeline and WAV path
 /// WAV path is needed for waveform visualization
 pub fn process_video_pipeline_with_wav(
     video_path: &str,
     config: &SilenceDetectionConfig,
+    scene_snap_config: &SceneSnapConfig,
     ffmpeg_path: &PathBuf,
     ffprobe_path: &PathBuf,
 ) -> Result<(Timeline, PathBuf), String> {
     log::info!("Starting video processing pipeline for: {}", video_path);
-    
+
     // Validate video file
 
-    
-    // Get video duration
-    let duration = get_video_duration(video_path, ffprobe_path)?;
-    log::info!("Video duration: {:.2} seconds", duration);
-    
+
+    // Get video duration, frame rate, and time base
+    let video_info = get_video_duration(video_path, ffprobe_path)?;
+    log::info!("Video duration: {:.2} seconds", video_info.duration);
+
     // Extract audio to temporary directory
     let temp_dir = env::temp_dir().join("video-silence-remover");
     let wav_path = extract_audio_to_wav(video_path, &temp_dir, ffmpeg_path)?;
-    
+
     // Detect silence
     let silence_ranges = detect_silence(&wav_path, config)?;
-    
+
     // Create timeline and split by silence
-    let mut timeline = Timeline::new(duration, video_path.to_string());
+    let mut timeline = Timeline::new(video_info.duration, video_path.to_string(), video_info.frame_rate, video_info.time_base);
     timeline.split_by_silence(silence_ranges);
-    
+
+    // Optionally snap cuts onto nearby scene changes so they don't land mid-motion
+    if scene_snap_config.enabled {
+        let scene_times = detect_scene_changes(video_path, ffmpeg_path, SCENE_DETECTION_THRESHOLD)?;
+        timeline.snap_cuts_to_scene_changes(&scene_times, scene_snap_config.tolerance_seconds);
+    }
+
     log::info!("Pipeline completed successfully");
     Ok((timeline, wav_path))
 }
@@ -0,0 +1,3 @@
+pub mod actions;
+
+pub use actions::process_video_pipeline_with_wav;
@@ -0,0 +1,185 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use tauri::{Emitter, Window};
+
+use crate::timeline::{Clip, Timeline};
+use super::ffmpeg_export::ExportProgress;
+
+/// Exports `included_clips` via pure demux-remux stream copy: each clip's
+/// `source_start` is snapped back to the nearest preceding keyframe so FFmpeg
+/// never has to decode a frame to honor the cut, then the segments are cut
+/// and stitched with the concat demuxer. Faster than `export_lossless` (no
+/// MP4 edit lists, no partial-GOP handling) at the cost of cuts landing on
+/// the nearest keyframe rather than the exact requested time.
+pub fn export_fast_lossless(
+    timeline: &Timeline,
+    included_clips: &[Clip],
+    output_path: &str,
+    ffmpeg_path: &PathBuf,
+    ffprobe_path: &PathBuf,
+    window: Window,
+) -> Result<String, String> {
+    let keyframe_times = probe_keyframe_times(&timeline.video_path, ffprobe_path)?;
+
+    let work_dir = env::temp_dir().join("video-silence-remover").join("fast-lossless-export");
+    fs::create_dir_all(&work_dir)
+        .map_err(|e| format!("Failed to create export work directory: {}", e))?;
+
+    let result = run_fast_lossless_export(timeline, included_clips, &keyframe_times, output_path, ffmpeg_path, &window, &work_dir);
+
+    // Clean up temp segments regardless of outcome
+    let _ = fs::remove_dir_all(&work_dir);
+
+    result
+}
+
+fn run_fast_lossless_export(
+    timeline: &Timeline,
+    included_clips: &[Clip],
+    keyframe_times: &[f64],
+    output_path: &str,
+    ffmpeg_path: &PathBuf,
+    window: &Window,
+    work_dir: &PathBuf,
+) -> Result<String, String> {
+    let total_duration: f64 = included_clips.iter()
+        .map(|clip| clip.source_end - clip.source_start)
+        .sum();
+
+    let mut segment_paths = Vec::with_capacity(included_clips.len());
+    let mut completed_duration = 0.0;
+
+    for (i, clip) in included_clips.iter().enumerate() {
+        let segment_path = work_dir.join(format!("segment-{:04}.mp4", i));
+        let keyframe_start = snap_to_preceding_keyframe(keyframe_times, clip.source_start);
+        let duration = clip.source_end - keyframe_start;
+
+        // Input-seeking to the keyframe plus stream copy: since the cut
+        // always lands on a keyframe, ffmpeg copies packets with no decode
+        let status = Command::new(ffmpeg_path)
+            .args(&[
+                "-ss", &keyframe_start.to_string(),
+                "-i", &timeline.video_path,
+                "-t", &duration.to_string(),
+                "-c", "copy",
+                "-avoid_negative_ts", "make_zero",
+                "-y",
+                segment_path.to_str().ok_or("Invalid temp segment path")?,
+            ])
+            .status()
+            .map_err(|e| format!("Failed to execute FFmpeg: {}", e))?;
+
+        if !status.success() {
+            return Err(format!("FFmpeg failed to stream-copy clip {}", i));
+        }
+
+        segment_paths.push(segment_path);
+
+        completed_duration += clip.source_end - clip.source_start;
+        let percentage = ((completed_duration / total_duration) * 100.0).min(100.0);
+        let _ = window.emit("export-progress", ExportProgress {
+            percentage,
+            current_time: completed_duration,
+            total_duration,
+        });
+    }
+
+    let list_path = work_dir.join("concat-list.txt");
+    let list_contents = segment_paths.iter()
+        .map(|path| format!("file '{}'", path.to_string_lossy().replace('\'', "'\\''")))
+        .collect::<Vec<_>>()
+        .join("\n");
+    fs::write(&list_path, list_contents)
+        .map_err(|e| format!("Failed to write concat list: {}", e))?;
+
+    let status = Command::new(ffmpeg_path)
+        .args(&[
+            "-f", "concat",
+            "-safe", "0",
+            "-i", list_path.to_str().ok_or("Invalid concat list path")?,
+            "-c", "copy",
+            "-y",
+            output_path,
+        ])
+        .status()
+        .map_err(|e| format!("Failed to execute FFmpeg: {}", e))?;
+
+    if !status.success() {
+        return Err("FFmpeg concat stitching failed".to_string());
+    }
+
+    log::info!("Fast lossless export completed successfully");
+    Ok(output_path.to_string())
+}
+
+/// Probes every keyframe (I-frame) timestamp in the source video's primary
+/// video stream via FFprobe, sorted ascending.
+fn probe_keyframe_times(video_path: &str, ffprobe_path: &PathBuf) -> Result<Vec<f64>, String> {
+    let output = Command::new(ffprobe_path)
+        .args(&[
+            "-v", "error",
+            "-select_streams", "v:0",
+            "-show_entries", "packet=pts_time,flags",
+            "-of", "csv=print_section=0",
+            video_path,
+        ])
+        .output()
+        .map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                "FFprobe not found. Please ensure FFmpeg (with FFprobe) is installed.".to_string()
+            } else {
+                format!("Failed to execute FFprobe: {}", e)
+            }
+        })?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("FFprobe failed: {}", stderr));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut keyframe_times: Vec<f64> = stdout.lines()
+        .filter_map(|line| {
+            let (pts_time, flags) = line.split_once(',')?;
+            flags.contains('K').then(|| pts_time.trim().parse::<f64>().ok()).flatten()
+        })
+        .collect();
+
+    keyframe_times.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    Ok(keyframe_times)
+}
+
+/// Finds the latest keyframe at or before `time`, falling back to the start
+/// of the file if there's no earlier keyframe.
+fn snap_to_preceding_keyframe(keyframe_times: &[f64], time: f64) -> f64 {
+    let idx = keyframe_times.partition_point(|&t| t <= time);
+    idx.checked_sub(1)
+        .and_then(|i| keyframe_times.get(i))
+        .copied()
+        .unwrap_or(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snap_to_preceding_keyframe_picks_closest_earlier() {
+        let keyframes = vec![0.0, 2.0, 4.0, 6.0];
+        assert_eq!(snap_to_preceding_keyframe(&keyframes, 5.0), 4.0);
+    }
+
+    #[test]
+    fn test_snap_to_preceding_keyframe_exact_match() {
+        let keyframes = vec![0.0, 2.0, 4.0, 6.0];
+        assert_eq!(snap_to_preceding_keyframe(&keyframes, 4.0), 4.0);
+    }
+
+    #[test]
+    fn test_snap_to_preceding_keyframe_before_first_falls_back_to_zero() {
+        let keyframes = vec![1.0, 3.0, 5.0];
+        assert_eq!(snap_to_preceding_keyframe(&keyframes, 0.5), 0.0);
+    }
+}
@@ -1,56 +1,129 @@
 use std::process::{Command, Stdio};
 use std::io::{BufRead, BufReader};
 use std::path::Path;
-use crate::timeline::Timeline;
+use crate::timeline::{Disposition, Timeline};
 use tauri::{Window, Emitter};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+
+use super::fast_lossless;
+use super::lossless;
+use super::parallel_reencode;
+
+/// Which pipeline `export_video` uses to turn the timeline into an output file
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ExportMode {
+    /// Re-encode the whole timeline through a single `filter_complex` (default, supports any edit)
+    Reencode,
+    /// Stream-copy each included clip and stitch with the concat demuxer, relying on
+    /// MP4 edit lists for cuts that don't land on a keyframe (cuts-only edits, near-instant)
+    Lossless,
+    /// Stream-copy each included clip after snapping its start back to the
+    /// nearest keyframe, so no MP4 edit list or partial-GOP handling is
+    /// needed at all. Faster than `Lossless`, but cuts land on a keyframe
+    /// rather than the exact requested time.
+    FastLossless,
+}
+
+impl Default for ExportMode {
+    fn default() -> Self {
+        ExportMode::Reencode
+    }
+}
 
 #[derive(Clone, Serialize)]
-struct ExportProgress {
-    percentage: f64,
-    current_time: f64,
-    total_duration: f64,
+pub(crate) struct ExportProgress {
+    pub percentage: f64,
+    pub current_time: f64,
+    pub total_duration: f64,
 }
 
-/// Exports the edited video based on the final timeline
-/// Uses FFmpeg's filter_complex to trim and concatenate clips
-/// Emits progress events to the frontend
-/// Only includes clips where include=true
+/// Exports the edited video based on the final timeline.
+/// Excludes clips with disposition `Drop`. Dispatches to the re-encode or
+/// lossless pipeline depending on `mode`. When `mode` is `Reencode` and
+/// `parallel` is true, the re-encode is split into per-clip chunks and run
+/// across up to `worker_count` (or `available_parallelism`) ffmpeg processes.
+/// `crossfade_ms`, when non-zero, blends consecutive clips with an
+/// `xfade`/`acrossfade` transition instead of a hard cut; it only applies to
+/// the single-process `Reencode` pipeline (the chunked and lossless pipelines
+/// encode clips independently and can't fuse them).
+///
+/// Fast-forwarded (`Disposition::Speed`) clips are only handled by the
+/// single-process `Reencode` pipeline, which applies `setpts`/`atempo` per
+/// clip. The chunked, lossless, and fast-lossless pipelines stream-copy or
+/// re-encode each clip independently at its source rate, so a `Speed` clip
+/// would silently export at 1x; rather than do that, this rejects any
+/// export with a sped-up clip outside `Reencode`, and also `Reencode` with
+/// `parallel` set.
 pub fn export_video(
     timeline: &Timeline,
     output_path: &str,
+    mode: ExportMode,
+    parallel: bool,
+    worker_count: Option<usize>,
+    crossfade_ms: Option<u32>,
     ffmpeg_path: &std::path::PathBuf,
+    ffprobe_path: &std::path::PathBuf,
     window: Window,
 ) -> Result<String, String> {
-    log::info!("Starting video export to: {}", output_path);
-    
-    // Filter to only include clips that are marked include=true
+    log::info!("Starting video export to: {} (mode: {:?}, parallel: {})", output_path, mode, parallel);
+
+    // Filter to only the clips that aren't dropped
     let included_clips: Vec<_> = timeline.clips.iter()
-        .filter(|clip| clip.include)
+        .filter(|clip| clip.is_included())
         .cloned()
         .collect();
-    
-    log::info!("Exporting {} of {} clips (include=true)", 
+
+    log::info!("Exporting {} of {} clips",
                included_clips.len(), timeline.clips.len());
-    
+
     if included_clips.is_empty() {
         return Err("Cannot export: no clips are included".to_string());
     }
-    
+
     // Verify input file exists
     if !Path::new(&timeline.video_path).exists() {
         return Err(format!("Source video file not found: {}", timeline.video_path));
     }
-    
+
+    let has_speed_clip = included_clips.iter().any(|clip| matches!(clip.disposition, Disposition::Speed(_)));
+    if has_speed_clip && !matches!(mode, ExportMode::Reencode if !parallel) {
+        return Err("Fast-forwarded clips are only supported by the single-process Reencode mode; disable parallel re-encode or remove the speed changes to use this export mode".to_string());
+    }
+
+    match mode {
+        ExportMode::Reencode if parallel && included_clips.len() > 1 => parallel_reencode::export_parallel_reencode(
+            &timeline.video_path, &included_clips, output_path, ffmpeg_path, window, worker_count,
+        ),
+        ExportMode::Reencode => export_reencode(timeline, &included_clips, crossfade_ms.unwrap_or(0), output_path, ffmpeg_path, window),
+        ExportMode::Lossless => lossless::export_lossless(timeline, &included_clips, output_path, ffmpeg_path, window),
+        ExportMode::FastLossless => fast_lossless::export_fast_lossless(timeline, &included_clips, output_path, ffmpeg_path, ffprobe_path, window),
+    }
+}
+
+/// Re-encodes the whole timeline through a single FFmpeg filter_complex
+/// Emits progress events to the frontend
+fn export_reencode(
+    timeline: &Timeline,
+    included_clips: &[crate::timeline::Clip],
+    crossfade_ms: u32,
+    output_path: &str,
+    ffmpeg_path: &std::path::PathBuf,
+    window: Window,
+) -> Result<String, String> {
+    let crossfade_duration = crossfade_ms as f64 / 1000.0;
+
     // Build FFmpeg filter_complex command using included clips only
-    let filter = build_filter_complex_from_clips(&included_clips)?;
-    
+    let filter = build_filter_complex_from_clips(included_clips, crossfade_duration)?;
+
     log::info!("FFmpeg filter: {}", filter);
-    
-    // Calculate total output duration for progress tracking
+
+    // Calculate total output duration for progress tracking, accounting for
+    // clips that are sped up rather than played back at their source length,
+    // and for the overlap eaten by each crossfade transition
     let total_duration: f64 = included_clips.iter()
-        .map(|clip| clip.source_end - clip.source_start)
-        .sum();
+        .map(|clip| clip.export_duration())
+        .sum::<f64>()
+        - crossfade_overlap_total(included_clips.len(), crossfade_duration);
     
     // Execute FFmpeg export with progress monitoring
     let mut child = Command::new(ffmpeg_path)
@@ -115,51 +188,149 @@ pub fn export_video(
 
 
 
-/// Builds the FFmpeg filter_complex string from a slice of clips
-fn build_filter_complex_from_clips(clips: &[crate::timeline::Clip]) -> Result<String, String> {
+/// Builds the FFmpeg filter_complex string from a slice of clips. When
+/// `crossfade_duration` is zero, consecutive clips are hard-cut together via
+/// `concat`; otherwise they're blended pairwise with `xfade`/`acrossfade`.
+fn build_filter_complex_from_clips(clips: &[crate::timeline::Clip], crossfade_duration: f64) -> Result<String, String> {
     let mut video_filters = Vec::new();
     let mut audio_filters = Vec::new();
     let mut concat_inputs = Vec::new();
-    
+
     for (i, clip) in clips.iter().enumerate() {
-        // Video trim filter
-        video_filters.push(format!(
-            "[0:v]trim=start={}:end={},setpts=PTS-STARTPTS[v{}]",
-            clip.source_start, clip.source_end, i
-        ));
-        
-        // Audio trim filter
-        audio_filters.push(format!(
-            "[0:a]atrim=start={}:end={},asetpts=PTS-STARTPTS[a{}]",
-            clip.source_start, clip.source_end, i
-        ));
-        
+        push_trim_filters(&mut video_filters, &mut audio_filters, clip, i);
         concat_inputs.push(format!("[v{}][a{}]", i, i));
     }
-    
-    // Combine all filters
+
     let mut filter = String::new();
-    
-    // Add video filters
+
     for vf in video_filters {
         filter.push_str(&vf);
         filter.push_str(";");
     }
-    
-    // Add audio filters
     for af in audio_filters {
         filter.push_str(&af);
         filter.push_str(";");
     }
-    
-    // Add concat filter
-    filter.push_str(&concat_inputs.join(""));
-    filter.push_str(&format!(
-        "concat=n={}:v=1:a=1[outv][outa]",
-        clips.len()
-    ));
-    
+
+    if crossfade_duration > 0.0 && clips.len() > 1 {
+        filter.push_str(&build_crossfade_chain(clips, crossfade_duration));
+    } else {
+        // Add concat filter
+        filter.push_str(&concat_inputs.join(""));
+        filter.push_str(&format!(
+            "concat=n={}:v=1:a=1[outv][outa]",
+            clips.len()
+        ));
+    }
+
     Ok(filter)
 }
 
+/// Pushes this clip's trim (and, for sped-up clips, tempo) filters onto the
+/// shared video/audio filter lists, labelling the outputs `[vI]`/`[aI]`.
+fn push_trim_filters(video_filters: &mut Vec<String>, audio_filters: &mut Vec<String>, clip: &crate::timeline::Clip, i: usize) {
+    match clip.disposition {
+        Disposition::Speed(multiplier) if multiplier > 0.0 && multiplier != 1.0 => {
+            // Video: trim then slow down PTS growth by the multiplier to play back faster
+            video_filters.push(format!(
+                "[0:v]trim=start={}:end={},setpts=(PTS-STARTPTS)/{}[v{}]",
+                clip.source_start, clip.source_end, multiplier, i
+            ));
+
+            // Audio: trim, then chain atempo filters (each clamped to ffmpeg's
+            // 0.5-2.0 range) whose factors multiply out to the requested speed
+            audio_filters.push(format!(
+                "[0:a]atrim=start={}:end={},asetpts=PTS-STARTPTS,{}[a{}]",
+                clip.source_start, clip.source_end, atempo_chain(multiplier), i
+            ));
+        }
+        _ => {
+            // Video trim filter
+            video_filters.push(format!(
+                "[0:v]trim=start={}:end={},setpts=PTS-STARTPTS[v{}]",
+                clip.source_start, clip.source_end, i
+            ));
+
+            // Audio trim filter
+            audio_filters.push(format!(
+                "[0:a]atrim=start={}:end={},asetpts=PTS-STARTPTS[a{}]",
+                clip.source_start, clip.source_end, i
+            ));
+        }
+    }
+}
+
+/// Chains `xfade` (video) and `acrossfade` (audio) pairwise across `[v0][a0]
+/// .. [vN][aN]`, blending each join over `duration` seconds instead of a hard
+/// cut. `xfade`'s `offset` is where the transition starts within the fused
+/// stream so far, so it's recomputed from the running fused duration as each
+/// pair is folded in; `acrossfade` infers its own offset from its inputs'
+/// lengths. The last pair's outputs are labelled `[outv][outa]` directly.
+fn build_crossfade_chain(clips: &[crate::timeline::Clip], duration: f64) -> String {
+    let last = clips.len() - 1;
+    let mut video_label = "v0".to_string();
+    let mut audio_label = "a0".to_string();
+    let mut fused_duration = clips[0].export_duration();
+
+    let mut filter = String::new();
+    for i in 1..clips.len() {
+        let is_last = i == last;
+        let out_video = if is_last { "outv".to_string() } else { format!("vx{}", i) };
+        let out_audio = if is_last { "outa".to_string() } else { format!("ax{}", i) };
+        let offset = (fused_duration - duration).max(0.0);
+
+        filter.push_str(&format!(
+            "[{}][v{}]xfade=transition=fade:duration={}:offset={}[{}];",
+            video_label, i, duration, offset, out_video
+        ));
+        filter.push_str(&format!(
+            "[{}][a{}]acrossfade=d={}[{}];",
+            audio_label, i, duration, out_audio
+        ));
+
+        video_label = out_video;
+        audio_label = out_audio;
+        fused_duration = fused_duration + clips[i].export_duration() - duration;
+    }
+
+    // Drop the trailing separator left by the loop above
+    filter.pop();
+    filter
+}
+
+/// Total seconds eaten by crossfade overlaps across `clip_count` clips
+/// (one overlap per join, `clip_count - 1` joins), used to correct the
+/// progress-tracking total duration to match the fused output length.
+fn crossfade_overlap_total(clip_count: usize, crossfade_duration: f64) -> f64 {
+    if crossfade_duration <= 0.0 || clip_count < 2 {
+        0.0
+    } else {
+        (clip_count - 1) as f64 * crossfade_duration
+    }
+}
+
+/// Builds a chain of `atempo=N` filters whose factors multiply out to
+/// `multiplier`, since a single `atempo` only accepts 0.5-2.0 (e.g. 8x
+/// becomes `atempo=2,atempo=2,atempo=2`).
+fn atempo_chain(multiplier: f64) -> String {
+    let mut factors = Vec::new();
+    let mut remaining = multiplier;
+
+    while remaining > 2.0 {
+        factors.push(2.0);
+        remaining /= 2.0;
+    }
+    while remaining < 0.5 {
+        factors.push(0.5);
+        remaining /= 0.5;
+    }
+    if factors.is_empty() || (remaining - 1.0).abs() > 1e-9 {
+        factors.push(remaining);
+    }
+
+    factors.iter()
+        .map(|f| format!("atempo={}", f))
+        .collect::<Vec<_>>()
+        .join(",")
+}
 
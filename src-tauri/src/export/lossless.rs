@@ -0,0 +1,109 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use tauri::{Emitter, Window};
+
+use crate::timeline::{Clip, Timeline};
+use super::ffmpeg_export::ExportProgress;
+
+/// Exports `included_clips` losslessly: each clip is stream-copied to its own
+/// temp segment (letting FFmpeg's MP4 muxer emit an edit list for any cut that
+/// falls between keyframes), then the segments are stitched with the concat
+/// demuxer. No frame is re-encoded, so this is near-instant for cuts-only edits.
+pub fn export_lossless(
+    timeline: &Timeline,
+    included_clips: &[Clip],
+    output_path: &str,
+    ffmpeg_path: &PathBuf,
+    window: Window,
+) -> Result<String, String> {
+    let work_dir = env::temp_dir().join("video-silence-remover").join("lossless-export");
+    fs::create_dir_all(&work_dir)
+        .map_err(|e| format!("Failed to create export work directory: {}", e))?;
+
+    let result = run_lossless_export(timeline, included_clips, output_path, ffmpeg_path, &window, &work_dir);
+
+    // Clean up temp segments regardless of outcome
+    let _ = fs::remove_dir_all(&work_dir);
+
+    result
+}
+
+fn run_lossless_export(
+    timeline: &Timeline,
+    included_clips: &[Clip],
+    output_path: &str,
+    ffmpeg_path: &PathBuf,
+    window: &Window,
+    work_dir: &PathBuf,
+) -> Result<String, String> {
+    let total_duration: f64 = included_clips.iter()
+        .map(|clip| clip.source_end - clip.source_start)
+        .sum();
+
+    let mut segment_paths = Vec::with_capacity(included_clips.len());
+    let mut completed_duration = 0.0;
+
+    for (i, clip) in included_clips.iter().enumerate() {
+        let segment_path = work_dir.join(format!("segment-{:04}.mp4", i));
+
+        // Output-seeking trim with stream copy: FFmpeg's MP4 muxer writes an
+        // edit list (elst) to hide any head/tail that isn't on a keyframe,
+        // so the kept content still starts/ends exactly at source_start/source_end.
+        let status = Command::new(ffmpeg_path)
+            .args(&[
+                "-i", &timeline.video_path,
+                "-ss", &clip.source_start.to_string(),
+                "-to", &clip.source_end.to_string(),
+                "-c", "copy",
+                "-avoid_negative_ts", "make_zero",
+                "-movflags", "+use_editlist",
+                "-y",
+                segment_path.to_str().ok_or("Invalid temp segment path")?,
+            ])
+            .status()
+            .map_err(|e| format!("Failed to execute FFmpeg: {}", e))?;
+
+        if !status.success() {
+            return Err(format!("FFmpeg failed to stream-copy clip {}", i));
+        }
+
+        segment_paths.push(segment_path);
+
+        completed_duration += clip.source_end - clip.source_start;
+        let percentage = ((completed_duration / total_duration) * 100.0).min(100.0);
+        let _ = window.emit("export-progress", ExportProgress {
+            percentage,
+            current_time: completed_duration,
+            total_duration,
+        });
+    }
+
+    let list_path = work_dir.join("concat-list.txt");
+    let list_contents = segment_paths.iter()
+        .map(|path| format!("file '{}'", path.to_string_lossy().replace('\'', "'\\''")))
+        .collect::<Vec<_>>()
+        .join("\n");
+    fs::write(&list_path, list_contents)
+        .map_err(|e| format!("Failed to write concat list: {}", e))?;
+
+    let status = Command::new(ffmpeg_path)
+        .args(&[
+            "-f", "concat",
+            "-safe", "0",
+            "-i", list_path.to_str().ok_or("Invalid concat list path")?,
+            "-c", "copy",
+            "-y",
+            output_path,
+        ])
+        .status()
+        .map_err(|e| format!("Failed to execute FFmpeg: {}", e))?;
+
+    if !status.success() {
+        return Err("FFmpeg concat stitching failed".to_string());
+    }
+
+    log::info!("Lossless export completed successfully");
+    Ok(output_path.to_string())
+}
@@ -0,0 +1,6 @@
+pub mod ffmpeg_export;
+mod fast_lossless;
+mod lossless;
+mod parallel_reencode;
+
+pub use ffmpeg_export::{export_video, ExportMode};
@@ -8,13 +8,14 @@ mod export;
 use std::sync::Mutex;
 use std::path::PathBuf;
 use tauri::State;
-use analysis::{SilenceDetectionConfig, WaveformData};
+use analysis::{SceneSnapConfig, SilenceDetectionConfig, WaveformData, SCENE_DETECTION_THRESHOLD};
 use timeline::Timeline;
 
 /// Application state to store the current timeline
 struct AppState {
     timeline: Mutex<Option<Timeline>>,
     silence_config: Mutex<SilenceDetectionConfig>,
+    scene_snap_config: Mutex<SceneSnapConfig>,
     /// Path to extracted WAV file for waveform generation
     wav_path: Mutex<Option<PathBuf>>,
 }
@@ -68,11 +69,13 @@ fn process_video(
     
     // Get current silence detection config
     let config = state.silence_config.lock().unwrap().clone();
-    
+    let scene_snap_config = state.scene_snap_config.lock().unwrap().clone();
+
     // Process the video
     let (mut timeline, wav_path) = editor::process_video_pipeline_with_wav(
-        &video_path, 
+        &video_path,
         &config,
+        &scene_snap_config,
         &sidecars.ffmpeg,
         &sidecars.ffprobe
     )?;
@@ -122,19 +125,38 @@ fn get_video_path(state: State<AppState>) -> Result<String, String> {
     }
 }
 
+/// Exports the timeline to `output_path` using the given `mode` (default
+/// `Reencode` when omitted). Fast-forwarded clips (`Disposition::Speed`)
+/// are only honored by the single-process `Reencode` pipeline with
+/// `parallel` off; exporting them through any other mode or with `parallel`
+/// set is rejected rather than silently dropping the speed change.
 #[tauri::command]
 fn export_video(
     output_path: String,
+    mode: Option<export::ExportMode>,
+    parallel: Option<bool>,
+    worker_count: Option<usize>,
+    crossfade_ms: Option<u32>,
     state: State<AppState>,
     sidecars: State<SidecarPaths>,
     window: tauri::Window,
 ) -> Result<String, String> {
     log::info!("Exporting video to: {}", output_path);
-    
+
     let timeline = state.timeline.lock().unwrap();
-    
+
     if let Some(ref timeline) = *timeline {
-        export::export_video(timeline, &output_path, &sidecars.ffmpeg, window)
+        export::export_video(
+            timeline,
+            &output_path,
+            mode.unwrap_or_default(),
+            parallel.unwrap_or_default(),
+            worker_count,
+            crossfade_ms,
+            &sidecars.ffmpeg,
+            &sidecars.ffprobe,
+            window,
+        )
     } else {
         Err("No timeline loaded".to_string())
     }
@@ -163,6 +185,41 @@ fn get_silence_config(state: State<AppState>) -> SilenceDetectionConfig {
     state.silence_config.lock().unwrap().clone()
 }
 
+/// Update scene-snap configuration
+#[tauri::command]
+fn update_scene_snap_config(
+    enabled: bool,
+    tolerance_seconds: f64,
+    state: State<AppState>,
+) -> Result<(), String> {
+    log::info!("Updating scene snap config: enabled={}, tolerance={} s", enabled, tolerance_seconds);
+
+    let mut config = state.scene_snap_config.lock().unwrap();
+    config.enabled = enabled;
+    config.tolerance_seconds = tolerance_seconds;
+
+    Ok(())
+}
+
+/// Get current scene-snap configuration
+#[tauri::command]
+fn get_scene_snap_config(state: State<AppState>) -> SceneSnapConfig {
+    state.scene_snap_config.lock().unwrap().clone()
+}
+
+/// Detects scene/shot-change boundaries in the currently loaded video, so the
+/// frontend can visualize them alongside the waveform peaks
+#[tauri::command]
+fn get_scene_boundaries(state: State<AppState>, sidecars: State<SidecarPaths>) -> Result<Vec<f64>, String> {
+    let timeline = state.timeline.lock().unwrap();
+    let video_path = timeline.as_ref()
+        .map(|t| t.video_path.clone())
+        .ok_or("No video loaded")?;
+    drop(timeline);
+
+    analysis::detect_scene_changes(&video_path, &sidecars.ffmpeg, SCENE_DETECTION_THRESHOLD)
+}
+
 // ============= NEW SEGMENT REVIEW COMMANDS =============
 
 /// Toggle include state for a segment
@@ -204,6 +261,19 @@ fn adjust_segment_boundary(index: usize, new_time: f64, state: State<AppState>)
     }
 }
 
+/// Set a segment to fast-forward at `multiplier`x speed instead of being kept or dropped
+#[tauri::command]
+fn set_segment_speed(index: usize, multiplier: f64, state: State<AppState>) -> Result<Timeline, String> {
+    let mut timeline_opt = state.timeline.lock().unwrap();
+
+    if let Some(ref mut timeline) = *timeline_opt {
+        timeline.set_segment_speed(index, multiplier)?;
+        Ok(timeline.clone())
+    } else {
+        Err("No timeline loaded".to_string())
+    }
+}
+
 /// Merge segment with the next segment
 #[tauri::command]
 fn merge_segments(index: usize, state: State<AppState>) -> Result<Timeline, String> {
@@ -227,13 +297,15 @@ fn rerun_analysis(state: State<AppState>, sidecars: State<SidecarPaths>) -> Resu
     drop(timeline_opt);
     
     let config = state.silence_config.lock().unwrap().clone();
-    
+    let scene_snap_config = state.scene_snap_config.lock().unwrap().clone();
+
     log::info!("Re-running analysis with threshold={} dB, min_duration={} s",
                config.threshold_db, config.min_silence_duration);
-    
+
     let (timeline, wav_path) = editor::process_video_pipeline_with_wav(
-        &video_path, 
+        &video_path,
         &config,
+        &scene_snap_config,
         &sidecars.ffmpeg,
         &sidecars.ffprobe
     )?;
@@ -301,6 +373,7 @@ pub fn run() {
         .manage(AppState {
             timeline: Mutex::new(None),
             silence_config: Mutex::new(SilenceDetectionConfig::default()),
+            scene_snap_config: Mutex::new(SceneSnapConfig::default()),
             wav_path: Mutex::new(None),
         })
         .manage(SidecarPaths {
@@ -314,9 +387,13 @@ pub fn run() {
             export_video,
             update_silence_config,
             get_silence_config,
+            update_scene_snap_config,
+            get_scene_snap_config,
+            get_scene_boundaries,
             get_video_path,
             // New segment review commands
             toggle_segment,
+            set_segment_speed,
             remove_segment,
             merge_segments,
             adjust_segment_boundary,
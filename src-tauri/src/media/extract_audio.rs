@@ -1,6 +1,7 @@
 use std::process::Command;
 use std::path::{Path, PathBuf};
 use std::fs;
+use serde::{Deserialize, Serialize};
 
 /// Extracts audio from a video file to WAV format using FFmpeg
 /// Returns the path to the extracted WAV file
@@ -62,16 +63,54 @@ pub fn extract_audio_to_wav(video_path: &str, output_dir: &Path, ffmpeg_path: &P
     Ok(output_path)
 }
 
-/// Gets the duration of a video file in seconds using FFprobe
-pub fn get_video_duration(video_path: &str, ffprobe_path: &Path) -> Result<f64, String> {
-    log::info!("Getting duration for: {}", video_path);
-    
-    // Use FFprobe to get video duration
+/// An exact rational number (numerator/denominator), used for frame rates and
+/// time bases so frame-boundary math never drifts the way a lossy `f64`
+/// conversion of e.g. 30000/1001 would.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Rational {
+    pub num: u32,
+    pub den: u32,
+}
+
+impl Rational {
+    pub fn as_f64(&self) -> f64 {
+        self.num as f64 / self.den as f64
+    }
+
+    /// Parses an FFprobe-style "num/den" rational string
+    fn parse(s: &str) -> Option<Self> {
+        let (num, den) = s.split_once('/')?;
+        Some(Rational {
+            num: num.trim().parse().ok()?,
+            den: den.trim().parse().ok()?,
+        })
+    }
+}
+
+/// Duration and frame-accurate timing info about a video's primary video
+/// stream, probed via FFprobe
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct VideoInfo {
+    /// Duration in seconds
+    pub duration: f64,
+    /// Nominal frame rate (FFprobe's `r_frame_rate`)
+    pub frame_rate: Rational,
+    /// Stream time base (FFprobe's `time_base`)
+    pub time_base: Rational,
+}
+
+/// Gets the duration, frame rate, and time base of a video file using FFprobe
+pub fn get_video_duration(video_path: &str, ffprobe_path: &Path) -> Result<VideoInfo, String> {
+    log::info!("Getting duration and frame timing for: {}", video_path);
+
+    // Use FFprobe to get duration from the container and frame timing from
+    // the first video stream in one pass
     let output = Command::new(ffprobe_path)
         .args(&[
             "-v", "error",
-            "-show_entries", "format=duration",
-            "-of", "default=noprint_wrappers=1:nokey=1",
+            "-select_streams", "v:0",
+            "-show_entries", "format=duration:stream=r_frame_rate,time_base",
+            "-of", "default=noprint_wrappers=1",
             video_path,
         ])
         .output()
@@ -82,17 +121,34 @@ pub fn get_video_duration(video_path: &str, ffprobe_path: &Path) -> Result<f64,
                 format!("Failed to execute FFprobe: {}", e)
             }
         })?;
-    
+
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
         return Err(format!("FFprobe failed: {}", stderr));
     }
-    
-    let duration_str = String::from_utf8_lossy(&output.stdout);
-    let duration = duration_str.trim()
-        .parse::<f64>()
-        .map_err(|e| format!("Failed to parse duration: {}", e))?;
-    
-    log::info!("Video duration: {} seconds", duration);
-    Ok(duration)
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    let mut duration = None;
+    let mut frame_rate = None;
+    let mut time_base = None;
+
+    for line in stdout.lines() {
+        let Some((key, value)) = line.split_once('=') else { continue };
+        match key {
+            "duration" => duration = value.trim().parse::<f64>().ok(),
+            "r_frame_rate" => frame_rate = Rational::parse(value),
+            "time_base" => time_base = Rational::parse(value),
+            _ => {}
+        }
+    }
+
+    let duration = duration.ok_or("Failed to parse duration from FFprobe output")?;
+    let frame_rate = frame_rate.ok_or("Failed to parse frame rate from FFprobe output")?;
+    let time_base = time_base.ok_or("Failed to parse time base from FFprobe output")?;
+
+    log::info!("Video duration: {} seconds, frame rate: {}/{}, time base: {}/{}",
+               duration, frame_rate.num, frame_rate.den, time_base.num, time_base.den);
+
+    Ok(VideoInfo { duration, frame_rate, time_base })
 }
@@ -0,0 +1,3 @@
+pub mod extract_audio;
+
+pub use extract_audio::{extract_audio_to_wav, get_video_duration, Rational, VideoInfo};
@@ -1,5 +1,16 @@
 use serde::{Deserialize, Serialize};
 
+/// What to do with a clip when exporting
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Disposition {
+    /// Play this clip at normal speed
+    Keep,
+    /// Drop this clip entirely from the export
+    Drop,
+    /// Play this clip at `N`x speed instead of dropping it (e.g. fast-forwarding silence)
+    Speed(f64),
+}
+
 /// Represents a clip segment on the timeline
 /// After silence deletion, timeline is contiguous but source has gaps.
 /// Each clip maps timeline time to source video time.
@@ -15,22 +26,22 @@ pub struct Clip {
     pub source_end: f64,
     /// Whether this clip represents silence
     pub is_silence: bool,
-    /// Whether to include this clip in export (checkbox state)
-    pub include: bool,
+    /// What to do with this clip in export (checkbox state / speed choice)
+    pub disposition: Disposition,
 }
 
 impl Clip {
     /// Creates a new clip with both timeline and source times
     pub fn new(source_start: f64, source_end: f64, is_silence: bool) -> Self {
         // Initially, timeline and source times are the same
-        // Content segments are included by default, silence excluded
+        // Content segments are kept by default, silence dropped
         Clip {
             timeline_start: source_start,
             timeline_end: source_end,
             source_start,
             source_end,
             is_silence,
-            include: !is_silence,
+            disposition: if is_silence { Disposition::Drop } else { Disposition::Keep },
         }
     }
 
@@ -39,6 +50,20 @@ impl Clip {
         self.source_end - self.source_start
     }
 
+    /// Returns the duration this clip actually contributes to the export,
+    /// i.e. `duration()` divided by the speed multiplier when sped up
+    pub fn export_duration(&self) -> f64 {
+        match self.disposition {
+            Disposition::Speed(multiplier) if multiplier > 0.0 => self.duration() / multiplier,
+            _ => self.duration(),
+        }
+    }
+
+    /// Whether this clip should appear in the export at all (anything but `Drop`)
+    pub fn is_included(&self) -> bool {
+        !matches!(self.disposition, Disposition::Drop)
+    }
+
     /// Validates that the clip has valid time boundaries
     pub fn is_valid(&self) -> bool {
         self.source_start >= 0.0 
@@ -72,11 +97,11 @@ mod tests {
         assert_eq!(clip.timeline_start, 0.0);
         assert_eq!(clip.timeline_end, 5.0);
         assert_eq!(clip.is_silence, false);
-        assert_eq!(clip.include, true); // Content clips are included by default
-        
+        assert_eq!(clip.disposition, Disposition::Keep); // Content clips are kept by default
+
         let silence_clip = Clip::new(0.0, 5.0, true);
         assert_eq!(silence_clip.is_silence, true);
-        assert_eq!(silence_clip.include, false); // Silence clips are excluded by default
+        assert_eq!(silence_clip.disposition, Disposition::Drop); // Silence clips are dropped by default
     }
 
     #[test]
@@ -85,6 +110,19 @@ mod tests {
         assert_eq!(clip.duration(), 5.0);
     }
 
+    #[test]
+    fn test_export_duration_divides_by_speed() {
+        let mut clip = Clip::new(0.0, 8.0, true);
+        clip.disposition = Disposition::Speed(4.0);
+        assert_eq!(clip.export_duration(), 2.0);
+    }
+
+    #[test]
+    fn test_export_duration_unaffected_for_kept_clips() {
+        let clip = Clip::new(0.0, 8.0, false);
+        assert_eq!(clip.export_duration(), 8.0);
+    }
+
     #[test]
     fn test_clip_validation() {
         let valid_clip = Clip::new(0.0, 1.0, false);
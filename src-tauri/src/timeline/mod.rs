@@ -0,0 +1,5 @@
+pub mod clip;
+pub mod timeline;
+
+pub use clip::{Clip, Disposition};
+pub use timeline::Timeline;
@@ -1,4 +1,5 @@
-use super::clip::Clip;
+use super::clip::{Clip, Disposition};
+use crate::media::Rational;
 use serde::{Deserialize, Serialize};
 
 /// Manages the timeline of video clips
@@ -9,11 +10,17 @@ pub struct Timeline {
     pub video_path: String,
     pub audio_path: Option<String>,
     pub raw_silence_ranges: Vec<(f64, f64)>,
+    /// Nominal frame rate of the source video, used to snap cut boundaries
+    /// to real frame edges
+    pub frame_rate: Rational,
+    /// Stream time base of the source video
+    pub time_base: Rational,
 }
 
 impl Timeline {
     /// Creates a new timeline with a single clip spanning the entire video
-    pub fn new(video_duration: f64, video_path: String) -> Self {
+    pub fn new(video_duration: f64, video_path: String, frame_rate: Rational, time_base: Rational) -> Self {
+        let video_duration = snap_time_to_frame(video_duration, frame_rate);
         let initial_clip = Clip::new(0.0, video_duration, false);
         Timeline {
             clips: vec![initial_clip],
@@ -21,9 +28,16 @@ impl Timeline {
             video_path,
             audio_path: None,
             raw_silence_ranges: Vec::new(),
+            frame_rate,
+            time_base,
         }
     }
 
+    /// Rounds `time` to the nearest frame boundary for this timeline's frame rate
+    pub fn snap_to_frame(&self, time: f64) -> f64 {
+        snap_time_to_frame(time, self.frame_rate)
+    }
+
     /// Splits the timeline based on detected silence ranges
     pub fn split_by_silence(&mut self, silence_ranges: Vec<(f64, f64)>) {
         self.raw_silence_ranges = silence_ranges.clone();
@@ -45,6 +59,8 @@ impl Timeline {
         
         // For each silence range, split any overlapping clips
         for (silence_start, silence_end) in sorted_silence {
+            let silence_start = self.snap_to_frame(silence_start);
+            let silence_end = self.snap_to_frame(silence_end);
             let mut temp_clips = Vec::new();
             
             for clip in current_clips.iter() {
@@ -85,6 +101,45 @@ impl Timeline {
         log::info!("Timeline now has {} clips after splitting", self.clips.len());
     }
 
+    /// Snaps every internal cut point (the boundary between two adjacent
+    /// clips) to the nearest detected scene change within `tolerance`
+    /// seconds, so silence-derived cuts land on a natural shot change
+    /// instead of mid-motion. Boundaries with no scene change nearby are
+    /// left untouched, and so is a scene change that would push the
+    /// boundary past either neighboring clip's own start/end (which would
+    /// otherwise yield a negative-duration clip when a short clip sits next
+    /// to a scene change within tolerance of the cut). A cut next to a
+    /// silence clip only moves into that silence, never out of it, so
+    /// snapping never grows a silence clip into the surrounding content.
+    /// `scene_times` must be sorted ascending.
+    pub fn snap_cuts_to_scene_changes(&mut self, scene_times: &[f64], tolerance: f64) {
+        if scene_times.is_empty() || self.clips.len() < 2 {
+            return;
+        }
+
+        for i in 0..self.clips.len() - 1 {
+            let boundary = self.clips[i].source_end;
+            if let Some(scene_time) = nearest_scene_time(scene_times, boundary, tolerance) {
+                let scene_time = self.snap_to_frame(scene_time);
+                let min_bound = self.clips[i].source_start;
+                let max_bound = self.clips[i + 1].source_end;
+                if scene_time <= min_bound || scene_time >= max_bound {
+                    continue;
+                }
+                // Only move a cut into a silence clip, never out of one
+                if (self.clips[i].is_silence && scene_time > boundary)
+                    || (self.clips[i + 1].is_silence && scene_time < boundary)
+                {
+                    continue;
+                }
+                self.clips[i].source_end = scene_time;
+                self.clips[i + 1].source_start = scene_time;
+            }
+        }
+
+        self.recalculate_timeline_times();
+    }
+
     /// Removes all clips marked as silence
     pub fn delete_silence_clips(&mut self) {
         let before_count = self.clips.len();
@@ -121,11 +176,33 @@ impl Timeline {
 
 
 
-    /// Toggles the include state of a segment at the given index
+    /// Toggles a segment between `Keep` and `Drop`. A segment currently set
+    /// to `Speed` is toggled to `Drop`, same as a kept segment.
     pub fn toggle_segment_include(&mut self, index: usize) -> Result<(), String> {
         if let Some(clip) = self.clips.get_mut(index) {
-            clip.include = !clip.include;
-            log::info!("Toggled segment {} include to {}", index, clip.include);
+            clip.disposition = match clip.disposition {
+                Disposition::Drop => Disposition::Keep,
+                _ => Disposition::Drop,
+            };
+            log::info!("Toggled segment {} disposition to {:?}", index, clip.disposition);
+            Ok(())
+        } else {
+            Err(format!("Segment index {} out of bounds", index))
+        }
+    }
+
+    /// Sets a segment to play back at `multiplier`x speed instead of being
+    /// kept at normal speed or dropped entirely
+    pub fn set_segment_speed(&mut self, index: usize, multiplier: f64) -> Result<(), String> {
+        if multiplier <= 0.0 {
+            return Err(format!("Speed multiplier must be positive, got {}", multiplier));
+        }
+        if multiplier == 1.0 {
+            return Err("Speed multiplier of 1.0 is just normal speed; use toggle_segment_include instead".to_string());
+        }
+        if let Some(clip) = self.clips.get_mut(index) {
+            clip.disposition = Disposition::Speed(multiplier);
+            log::info!("Set segment {} to {}x speed", index, multiplier);
             Ok(())
         } else {
             Err(format!("Segment index {} out of bounds", index))
@@ -170,6 +247,8 @@ impl Timeline {
             return Err("Cannot adjust boundary: no next segment".to_string());
         }
 
+        let new_time = self.snap_to_frame(new_time);
+
         // Validate new_time is within reasonable bounds (not before start of current, not after end of next)
         // We use a small epsilon or let strict validation happen in Clip
         let current_start = self.clips[index].source_start;
@@ -245,19 +324,50 @@ impl Timeline {
         
         // 2. Re-apply splitting with new ranges
         self.apply_silence_splitting(effective_silence_ranges);
-        
+
         // 3. Recalculate timeline times
         self.recalculate_timeline_times();
     }
+
+}
+
+/// Rounds `time` to the nearest frame boundary for `frame_rate`, so cuts
+/// land on a real frame instead of an arbitrary fractional second.
+fn snap_time_to_frame(time: f64, frame_rate: Rational) -> f64 {
+    let fps_num = frame_rate.num as f64;
+    let fps_den = frame_rate.den as f64;
+    (time * fps_num / fps_den).round() * fps_den / fps_num
+}
+
+/// Finds the scene boundary closest to `target` in a sorted list, if one
+/// falls within `tolerance` seconds.
+fn nearest_scene_time(sorted_scene_times: &[f64], target: f64, tolerance: f64) -> Option<f64> {
+    let idx = sorted_scene_times.partition_point(|&t| t < target);
+
+    [idx.checked_sub(1), Some(idx)]
+        .into_iter()
+        .flatten()
+        .filter_map(|i| sorted_scene_times.get(i))
+        .copied()
+        .map(|t| (t, (t - target).abs()))
+        .filter(|&(_, diff)| diff <= tolerance)
+        .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
+        .map(|(t, _)| t)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// 30fps, matching most common-case test fixtures; cut points in these
+    /// tests land on exact frame boundaries so snapping is a no-op
+    fn new_test_timeline(duration: f64, path: &str) -> Timeline {
+        Timeline::new(duration, path.to_string(), Rational { num: 30, den: 1 }, Rational { num: 1, den: 30 })
+    }
+
     #[test]
     fn test_timeline_creation() {
-        let timeline = Timeline::new(10.0, "test.mp4".to_string());
+        let timeline = new_test_timeline(10.0, "test.mp4");
         assert_eq!(timeline.clips.len(), 1);
         assert_eq!(timeline.total_duration, 10.0);
         assert_eq!(timeline.clips[0].source_start, 0.0);
@@ -266,11 +376,11 @@ mod tests {
 
     #[test]
     fn test_split_by_silence() {
-        let mut timeline = Timeline::new(10.0, "test.mp4".to_string());
-        
+        let mut timeline = new_test_timeline(10.0, "test.mp4");
+
         // Add silence from 3.0 to 5.0
         timeline.split_by_silence(vec![(3.0, 5.0)]);
-        
+
         // Should have 3 clips: [0-3], [3-5 silence], [5-10]
         assert_eq!(timeline.clips.len(), 3);
         assert_eq!(timeline.clips[0].is_silence, false);
@@ -280,13 +390,62 @@ mod tests {
 
     #[test]
     fn test_delete_silence() {
-        let mut timeline = Timeline::new(10.0, "test.mp4".to_string());
+        let mut timeline = new_test_timeline(10.0, "test.mp4");
         timeline.split_by_silence(vec![(3.0, 5.0)]);
-        
+
         timeline.delete_silence_clips();
-        
+
         // Should have 2 clips left
         assert_eq!(timeline.clips.len(), 2);
         assert!(timeline.clips.iter().all(|c| !c.is_silence));
     }
+
+    #[test]
+    fn test_snap_cuts_to_scene_changes() {
+        let mut timeline = new_test_timeline(10.0, "test.mp4");
+        timeline.split_by_silence(vec![(3.0, 5.0)]);
+
+        // Scene change at 3.2s is within tolerance of the 3.0s cut
+        timeline.snap_cuts_to_scene_changes(&[3.2], 0.25);
+
+        assert_eq!(timeline.clips[0].source_end, 3.2);
+        assert_eq!(timeline.clips[1].source_start, 3.2);
+        // The 5.0s cut had no nearby scene change, so it's untouched
+        assert_eq!(timeline.clips[1].source_end, 5.0);
+    }
+
+    #[test]
+    fn test_snap_cuts_rejects_scene_change_past_a_short_neighbor() {
+        let mut timeline = new_test_timeline(10.0, "test.mp4");
+        // A single-frame (1/30s) silence clip sits right after the cut at 3.0s
+        let short_clip_end = 91.0 / 30.0;
+        timeline.split_by_silence(vec![(3.0, short_clip_end)]);
+
+        // 3.2s is within tolerance of the 3.0s boundary, but it's past the
+        // short clip's own end; snapping there would leave that clip with
+        // source_start > source_end, so it must be rejected
+        timeline.snap_cuts_to_scene_changes(&[3.2], 0.25);
+
+        assert_eq!(timeline.clips[0].source_end, 3.0);
+        assert_eq!(timeline.clips[1].source_start, 3.0);
+        assert_eq!(timeline.clips[1].source_end, short_clip_end);
+    }
+
+    #[test]
+    fn test_snap_cuts_ignores_far_scene_changes() {
+        let mut timeline = new_test_timeline(10.0, "test.mp4");
+        timeline.split_by_silence(vec![(3.0, 5.0)]);
+
+        timeline.snap_cuts_to_scene_changes(&[1.0, 8.0], 0.25);
+
+        assert_eq!(timeline.clips[0].source_end, 3.0);
+        assert_eq!(timeline.clips[1].source_end, 5.0);
+    }
+
+    #[test]
+    fn test_snap_to_frame_rounds_to_nearest_frame() {
+        let timeline = new_test_timeline(10.0, "test.mp4");
+        // 3.01s is closer to frame 90 (3.0s) than frame 91 (3.0333...s) at 30fps
+        assert!((timeline.snap_to_frame(3.01) - 3.0).abs() < 1e-9);
+    }
 }